@@ -1,15 +1,21 @@
 use std::cmp::Ordering;
 use std::ffi::OsStr;
-use std::error::Error;
 use std::fs;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
 use std::path::{Path, PathBuf};
 use std::process::exit;
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+use std::sync::Arc;
+use std::thread;
 use clap::Parser;
 use markdown::Options;
 use markdown::CompileOptions;
+use notify::{RecursiveMode, Watcher};
+use rayon::prelude::*;
 use toml::value::Date;
 
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Clone)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
 	/// Path to prologue file.
@@ -20,7 +26,8 @@ struct Cli {
 	#[arg(short, long)]
 	epilogue: Option<PathBuf>,
 	
-	/// Path to output file. If not provided, an html file is produced adjacent to the input.
+	/// Path to output file, or output directory when converting a whole directory.
+	/// If not provided, output is produced adjacent to the input.
 	#[arg(short, long)]
 	output: Option<PathBuf>,
 
@@ -32,10 +39,72 @@ struct Cli {
 	#[arg(short, long)]
 	tags: Option<PathBuf>,
 
+	/// Path to Atom feed output file. Requires --index, since the feed reuses the
+	/// index's title and base URL.
+	#[arg(short, long)]
+	feed: Option<PathBuf>,
+
+	/// Build the input directory once, then serve the output over HTTP and rebuild
+	/// whenever the input tree changes, reloading open pages automatically.
+	#[arg(long)]
+	serve: bool,
+
+	/// Port to serve on when --serve is set.
+	#[arg(long, default_value_t = 8080)]
+	port: u16,
+
 	/// Path to markdown document, or directory containing markdown documents.
 	input: PathBuf,
 }
 
+fn date_to_rfc3339(date: &Date) -> String {
+	format!("{:04}-{:02}-{:02}T00:00:00Z", date.year, date.month, date.day)
+}
+
+fn escape_html(text: &str) -> String {
+	text
+		.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+}
+
+// Backslash-escapes CommonMark punctuation so text built into intermediate
+// Markdown (e.g. the generated index) can't break link syntax or, combined
+// with allow_dangerous_html, smuggle in raw HTML tags.
+fn escape_markdown(text: &str) -> String {
+	let mut escaped = String::with_capacity(text.len());
+	for c in text.chars() {
+		if matches!(c, '\\' | '[' | ']' | '(' | ')' | '<' | '>' | '&' | '"' | '`' | '*' | '_') {
+			escaped.push('\\');
+		}
+		escaped.push(c);
+	}
+	escaped
+}
+
+// Polls the dev server for the current build version and reloads once it changes.
+const LIVE_RELOAD_SCRIPT: &str = r#"<script>
+(function() {
+	let lastVersion = null;
+	async function poll() {
+		try {
+			let response = await fetch("/__evblog_reload");
+			let version = await response.text();
+			if (lastVersion === null) {
+				lastVersion = version;
+			} else if (version !== lastVersion) {
+				location.reload();
+				return;
+			}
+		} catch (e) {}
+		setTimeout(poll, 1000);
+	}
+	poll();
+})();
+</script>
+"#;
+
 fn date_to_english(date: &Date) -> String {
 	format!(
 		"{} {}{}, {}",
@@ -71,6 +140,10 @@ struct Metadata {
 	tags: Vec<String>,
 	publish_date: Option<Date>,
 	file_name: PathBuf,
+	draft: bool,
+	slug: Option<String>,
+	description: Option<String>,
+	order: Option<i64>,
 }
 
 impl Metadata {
@@ -80,6 +153,10 @@ impl Metadata {
 			tags: Vec::new(),
 			publish_date: None,
 			file_name: PathBuf::new(),
+			draft: false,
+			slug: None,
+			description: None,
+			order: None,
 		}
 	}
 
@@ -112,6 +189,22 @@ impl Metadata {
 			}
 		}
 
+		if let Some(toml::Value::Boolean(draft)) = &toml.get("draft") {
+			metadata.draft = *draft;
+		}
+
+		if let Some(toml::Value::String(slug)) = &toml.get("slug") {
+			metadata.slug = Some(slug.clone());
+		}
+
+		if let Some(toml::Value::String(description)) = &toml.get("description") {
+			metadata.description = Some(description.clone());
+		}
+
+		if let Some(toml::Value::Integer(order)) = &toml.get("order") {
+			metadata.order = Some(*order);
+		}
+
 		metadata
 	}
 }
@@ -122,9 +215,18 @@ struct Tag {
 	description: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortMode {
+	Date,
+	Order,
+	Title,
+}
+
 #[derive(Debug)]
 struct IndexConfig {
 	title: String,
+	base_url: String,
+	sort: SortMode,
 	tags: Vec<Tag>,
 }
 
@@ -132,6 +234,8 @@ impl IndexConfig {
 	fn new() -> Self {
 		Self {
 			title: String::new(),
+			base_url: String::new(),
+			sort: SortMode::Date,
 			tags: Vec::new(),
 		}
 	}
@@ -149,6 +253,26 @@ impl IndexConfig {
 						{
 							config.title = title.to_string();
 						}
+						if let Some(base_url) = table
+							.get("base_url")
+							.map_or(None, |s| s.as_str())
+						{
+							config.base_url = base_url.to_string();
+						}
+						if let Some(sort) = table
+							.get("sort")
+							.map_or(None, |s| s.as_str())
+						{
+							config.sort = match sort {
+								"date" => SortMode::Date,
+								"order" => SortMode::Order,
+								"title" => SortMode::Title,
+								other => {
+									eprintln!("Unknown sort mode \"{other}\", defaulting to \"date\"");
+									SortMode::Date
+								}
+							};
+						}
 						if let Some(tags) = table
 							.get("tag")
 							.map_or(None, |s| s.as_array())
@@ -181,32 +305,125 @@ impl IndexConfig {
 	}
 }
 
-fn optional_file_concat(out: &mut String, path: Option<impl AsRef<Path>>) -> Result<(), Box<dyn Error>> {
+fn optional_file_concat(out: &mut String, path: Option<impl AsRef<Path>>) -> Result<(), String> {
 	if let Some(path) = path {
-		*out += &fs::read_to_string(path)?;
+		let path = path.as_ref();
+		*out += &fs::read_to_string(path)
+			.map_err(|err| format!("Failed to read {}: {}", path.display(), err))?;
 	}
 	Ok(())
 }
 
+/// Builds a document as a flat, ordered stream of HTML fragments, escaping text
+/// nodes and attribute values as they're added. evblog has never owned the
+/// surrounding `<html>`/`<head>`/`<body>` structure itself: the prologue opens
+/// it and the epilogue closes it, so this type only owns escaping, not the
+/// wrapper. Trusted HTML (markdown output, the prologue/epilogue, the
+/// live-reload script) is appended via `push_raw_body` without escaping.
+struct HtmlDocument {
+	content: String,
+}
+
+impl HtmlDocument {
+	fn new() -> Self {
+		Self {
+			content: String::new(),
+		}
+	}
+
+	fn push_description(&mut self, description: &str) {
+		self.content += &format!("<meta name=\"description\" content=\"{}\">\n", escape_html(description));
+	}
+
+	fn push_title(&mut self, title: &str) {
+		self.content += &format!("<h1><center> {} </center></h1>\n", escape_html(title));
+	}
+
+	fn push_raw_body(&mut self, html: &str) {
+		self.content += html;
+	}
+
+	fn render(self) -> String {
+		self.content
+	}
+}
+
+fn generate_feed(title: &str, base_url: &str, article_data: &[Metadata]) -> String {
+	let base_url = base_url.trim_end_matches('/');
+
+	// The feed is always newest-first, regardless of the index's configured sort mode.
+	let mut article_data: Vec<&Metadata> = article_data.iter().collect();
+	article_data.sort_by(|a, b| match (a.publish_date, b.publish_date) {
+		(Some(_), None) => Ordering::Less,
+		(None, Some(_)) => Ordering::Greater,
+		(None, None) => Ordering::Equal,
+		(Some(a_date), Some(b_date)) => {
+			b_date.year.cmp(&a_date.year)
+				.then(b_date.month.cmp(&a_date.month))
+				.then(b_date.day.cmp(&a_date.day))
+		}
+	});
+
+	let mut feed = String::new();
+
+	feed += "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n";
+	feed += "<feed xmlns=\"http://www.w3.org/2005/Atom\">\n";
+	feed += &format!("\t<title>{}</title>\n", escape_html(title));
+	feed += &format!("\t<link rel=\"self\" href=\"{}\"/>\n", escape_html(base_url));
+	feed += &format!("\t<id>{}</id>\n", escape_html(base_url));
+
+	let latest_date = article_data
+		.iter()
+		.filter_map(|article| article.publish_date)
+		.max_by(|a, b| {
+			a.year.cmp(&b.year)
+				.then(a.month.cmp(&b.month))
+				.then(a.day.cmp(&b.day))
+		});
+
+	if let Some(date) = latest_date {
+		feed += &format!("\t<updated>{}</updated>\n", date_to_rfc3339(&date));
+	}
+
+	for article in article_data {
+		let date = match article.publish_date {
+			Some(date) => date,
+			None => continue,
+		};
+		let title = match &article.title {
+			Some(title) => title,
+			None => continue,
+		};
+
+		let link = format!("{}/{}", base_url, article.file_name.display());
+
+		feed += "\t<entry>\n";
+		feed += &format!("\t\t<title>{}</title>\n", escape_html(title));
+		feed += &format!("\t\t<link href=\"{}\"/>\n", escape_html(&link));
+		feed += &format!("\t\t<id>urn:evblog:{}</id>\n", escape_html(&link));
+		feed += &format!("\t\t<updated>{}</updated>\n", date_to_rfc3339(&date));
+		feed += &format!("\t\t<published>{}</published>\n", date_to_rfc3339(&date));
+		feed += "\t</entry>\n";
+	}
+
+	feed += "</feed>\n";
+
+	feed
+}
+
 fn convert_document(
 	cli: &Cli,
 	infile: &Path,
 	outfile: &Path,
-) -> Metadata {
-	// Oh how I long for let/else in stable Rust.
-	let document = match fs::read_to_string(&infile) {
-		// Like seriously how'd it take this long. This is so silly...
-		Ok(document) => document,
-		Err(err) => {
-			eprintln!("Failed to read {}: {}", infile.display(), err);
-			exit(1)
-		}
-	};
+	is_article: bool,
+) -> Result<Metadata, String> {
+	let document_text = fs::read_to_string(infile)
+		.map_err(|err| format!("Failed to read {}: {}", infile.display(), err))?;
 
 	let mut metadata = String::new();
 
-	if document.starts_with("<!-- metadata") {
-		let mut line_iter = document.split("\n");
+	if document_text.starts_with("<!-- metadata") {
+		let mut line_iter = document_text.split("\n");
 		line_iter.next();
 		for line in line_iter {
 			if line == "-->" { break; }
@@ -216,21 +433,42 @@ fn convert_document(
 	}
 
 	let mut metadata = Metadata::from_toml(metadata);
+
+	let outfile = if let Some(slug) = &metadata.slug {
+		outfile.with_file_name(format!("{slug}.html"))
+	} else {
+		outfile.to_path_buf()
+	};
 	metadata.file_name = outfile.file_name().unwrap().into();
 
-	let mut html = String::new();
+	// Drafts are kept out of the built site entirely: no output file, and (since
+	// they're never pushed into article_data by the caller) no index or feed entry.
+	if metadata.draft && is_article {
+		return Ok(metadata);
+	}
+
+	let mut document = HtmlDocument::new();
 
-	// Prologue
-	optional_file_concat(&mut html, cli.prologue.as_deref()).unwrap();
+	// Prologue: opens the surrounding <html>/<head>/<body>, which evblog itself
+	// never generates, so everything below must land after it and before the
+	// epilogue closes those tags.
+	let mut prologue = String::new();
+	optional_file_concat(&mut prologue, cli.prologue.as_deref())?;
+	document.push_raw_body(&prologue);
+
+	// Description
+	if let Some(description) = &metadata.description {
+		document.push_description(description);
+	}
 
 	// Title
 	if let Some(title) = &metadata.title {
-		html += &format!("<h1><center> {title} </center></h1>\n");
+		document.push_title(title);
 	}
 
 	// Body
-	html += &markdown::to_html_with_options(
-		&document,
+	document.push_raw_body(&markdown::to_html_with_options(
+		&document_text,
 		&Options {
 			compile: CompileOptions {
 			  allow_dangerous_html: true,
@@ -238,87 +476,398 @@ fn convert_document(
 			},
 			..Options::gfm()
 		}
-	).unwrap();
+	).unwrap());
 
 	// Epilogue
-	optional_file_concat(&mut html, cli.epilogue.as_deref()).unwrap();
+	let mut epilogue = String::new();
+	optional_file_concat(&mut epilogue, cli.epilogue.as_deref())?;
+	document.push_raw_body(&epilogue);
+
+	// Live reload (dev server only)
+	if cli.serve {
+		document.push_raw_body(LIVE_RELOAD_SCRIPT);
+	}
+
+	fs::write(&outfile, document.render())
+		.map_err(|err| format!("Failed to write to {}: {}", outfile.display(), err))?;
+
+	Ok(metadata)
+}
+
+/// Copies an `.html` template file, substituting `%prologue%`/`%header%` and
+/// `%epilogue%`/`%footer%` tokens with the configured prologue/epilogue contents.
+fn copy_template(cli: &Cli, infile: &Path, outfile: &Path) {
+	let mut html = match fs::read_to_string(infile) {
+		Ok(html) => html,
+		Err(err) => {
+			eprintln!("Failed to read {}: {}", infile.display(), err);
+			exit(1)
+		}
+	};
+
+	if let Some(prologue) = cli.prologue.as_deref() {
+		if let Ok(prologue) = fs::read_to_string(prologue) {
+			html = html.replace("%prologue%", &prologue).replace("%header%", &prologue);
+		}
+	}
 
-	if let Err(err) = fs::write(&outfile, html) {
+	if let Some(epilogue) = cli.epilogue.as_deref() {
+		if let Ok(epilogue) = fs::read_to_string(epilogue) {
+			html = html.replace("%epilogue%", &epilogue).replace("%footer%", &epilogue);
+		}
+	}
+
+	if let Err(err) = fs::write(outfile, html) {
 		eprintln!("Failed to write to {}: {}", outfile.display(), err);
 		exit(1)
 	}
-
-	metadata
 }
 
-fn main() {
-	let cli = Cli::parse();
+/// Recursively mirrors `dir` (somewhere under `input_root`) into `output_root`,
+/// templating `.html` files and copying everything else verbatim. Markdown files
+/// are left unconverted; their (input, output) paths are appended to
+/// `markdown_files` so the caller can convert them in parallel.
+fn collect_markdown_files(
+	cli: &Cli,
+	input_root: &Path,
+	output_root: &Path,
+	dir: &Path,
+	markdown_files: &mut Vec<(PathBuf, PathBuf)>,
+) {
+	for entry in fs::read_dir(dir).unwrap() {
+		if let Err(_) = entry { continue; }
+		let entry = entry.unwrap();
+		let path = entry.path();
+
+		if path.is_dir() {
+			collect_markdown_files(cli, input_root, output_root, &path, markdown_files);
+			continue;
+		}
 
-	if fs::metadata(&cli.input).unwrap().is_dir() {
-		let mut article_data = Vec::<Metadata>::new();
+		let relative = path.strip_prefix(input_root).unwrap();
+		let output_path = output_root.join(relative);
 
-		for entry in fs::read_dir(&cli.input).unwrap() {
-			if let Err(_) = entry { continue; }
-			let entry = entry.unwrap();
-			if entry.path().extension() != Some(&OsStr::new("md")) { continue; }
+		if let Some(parent) = output_path.parent() {
+			fs::create_dir_all(parent).unwrap();
+		}
 
-			let input = entry.path();
-			let mut output = entry.path().to_path_buf();
-			output.set_extension("html");
-			
-			let metadata = convert_document(&cli, &input, &output);
-			article_data.push(metadata);
+		match path.extension() {
+			Some(extension) if extension == OsStr::new("md") => {
+				let mut output_path = output_path;
+				output_path.set_extension("html");
+				markdown_files.push((path, output_path));
+			}
+			Some(extension) if extension == OsStr::new("html") => {
+				copy_template(cli, &path, &output_path);
+			}
+			_ => {
+				// Building in place (no --output) makes every asset its own destination;
+				// fs::copy onto the same file would truncate it, so skip those.
+				let already_in_place = match (fs::canonicalize(&path), fs::canonicalize(&output_path)) {
+					(Ok(path), Ok(output_path)) => path == output_path,
+					_ => false,
+				};
+
+				if !already_in_place {
+					if let Err(err) = fs::copy(&path, &output_path) {
+						eprintln!("Failed to copy {} to {}: {}", path.display(), output_path.display(), err);
+						exit(1)
+					}
+				}
+			}
 		}
+	}
+}
+
+/// Builds the whole input directory into `cli.output` (or in place if unset),
+/// returning the output root so callers (e.g. the dev server) know what to serve.
+fn build_directory(cli: &Cli) -> PathBuf {
+	let index_config = cli.index.as_deref().map(IndexConfig::open);
+	let output_root = cli.output.clone().unwrap_or_else(|| cli.input.clone());
+
+	let mut markdown_files = Vec::<(PathBuf, PathBuf)>::new();
+	collect_markdown_files(cli, &cli.input, &output_root, &cli.input, &mut markdown_files);
+
+	let mut article_data = Vec::<Metadata>::new();
+	for (result, (input, _)) in markdown_files
+		.par_iter()
+		.map(|(input, output)| convert_document(cli, input, output, true))
+		.collect::<Vec<_>>()
+		.into_iter()
+		.zip(&markdown_files)
+	{
+		let metadata = match result {
+			Ok(metadata) => metadata,
+			Err(err) => {
+				eprintln!("{err}");
+				exit(1)
+			}
+		};
 
-		article_data.sort_by(|b, a| {
+		if metadata.draft { continue; }
+
+		let relative = input.strip_prefix(&cli.input).unwrap();
+		let relative_dir = relative.parent().unwrap_or(Path::new(""));
+		article_data.push(Metadata {
+			file_name: relative_dir.join(&metadata.file_name),
+			..metadata
+		});
+	}
+
+	let sort_mode = index_config.as_ref().map_or(SortMode::Date, |config| config.sort);
+
+	article_data.sort_by(|a, b| match sort_mode {
+		SortMode::Date => {
 			let (a_date, b_date) = match (a.publish_date, b.publish_date) {
-				(Some(_), None) => return Ordering::Greater,
-				(None, Some(_)) => return Ordering::Less,
+				(Some(_), None) => return Ordering::Less,
+				(None, Some(_)) => return Ordering::Greater,
 				(None, None) => return Ordering::Equal,
 				(Some(a_date), Some(b_date)) => (a_date, b_date),
 			};
 
-			let year_cmp = a_date.year.cmp(&b_date.year);
-			if year_cmp != Ordering::Equal { return year_cmp; }
-			let month_cmp = a_date.month.cmp(&b_date.month);
-			if month_cmp != Ordering::Equal { return month_cmp; }
-			a_date.day.cmp(&b_date.day)
-		});
+			// Newest first.
+			b_date.year.cmp(&a_date.year)
+				.then(b_date.month.cmp(&a_date.month))
+				.then(b_date.day.cmp(&a_date.day))
+		}
+		SortMode::Order => match (a.order, b.order) {
+			(Some(a_order), Some(b_order)) => a_order.cmp(&b_order),
+			(Some(_), None) => Ordering::Less,
+			(None, Some(_)) => Ordering::Greater,
+			(None, None) => Ordering::Equal,
+		}
+		SortMode::Title => match (&a.title, &b.title) {
+			(Some(a_title), Some(b_title)) => a_title.cmp(b_title),
+			(Some(_), None) => Ordering::Less,
+			(None, Some(_)) => Ordering::Greater,
+			(None, None) => Ordering::Equal,
+		}
+	});
 
-		if let Some(index_config_path) = &cli.index {
-			let index_config = IndexConfig::open(index_config_path);
-			let mut index_md = String::new();
+	if let Some(index_config) = index_config {
+		let mut index_md = String::new();
 
-			index_md += &format!("# <center> {} </center>\n", index_config.title);
+		index_md += &format!("# <center> {} </center>\n", escape_markdown(&index_config.title));
 
-			for tag in index_config.tags {
-				index_md += &format!("## {}\n{}\n", tag.name, tag.description);
+		let index_title = index_config.title.clone();
+		let index_base_url = index_config.base_url.clone();
 
-				for article in article_data.iter().filter(|a| a.tags.contains(&tag.name)) {
-					let title = if let Some(title) = &article.title {
-						title
-					} else {
-						continue;
-					};
-					index_md += &format!("- [{title}]({})", article.file_name.display());
-					if let Some(date) = article.publish_date {
-						index_md += &format!("<br>{}", date_to_english(&date));
-					}
-					index_md += "\n";
+		for tag in index_config.tags {
+			index_md += &format!("## {}\n{}\n", escape_markdown(&tag.name), escape_markdown(&tag.description));
+
+			for article in article_data.iter().filter(|a| a.tags.contains(&tag.name)) {
+				let title = if let Some(title) = &article.title {
+					title
+				} else {
+					continue;
 				};
+				let title = escape_markdown(title);
+				let link = escape_markdown(&article.file_name.display().to_string());
+				index_md += &format!("- [{title}]({link})");
+				if let Some(date) = article.publish_date {
+					index_md += &format!("<br>{}", date_to_english(&date));
+				}
+				index_md += "\n";
+			};
+		}
+
+		let mut index_md_path = output_root.to_path_buf();
+		index_md_path.push("index.md");
+		let mut index_html_path = output_root.to_path_buf();
+		index_html_path.push("index.html");
+
+		if let Err(err) = fs::write(&index_md_path, index_md) {
+			eprintln!("Failed to write to {}: {}", index_md_path.display(), err);
+			exit(1)
+		}
+
+		if let Err(err) = convert_document(cli, &index_md_path, &index_html_path, false) {
+			eprintln!("{err}");
+			exit(1)
+		}
+
+		if let Some(feed_path) = &cli.feed {
+			let feed_xml = generate_feed(&index_title, &index_base_url, &article_data);
+
+			if let Err(err) = fs::write(feed_path, feed_xml) {
+				eprintln!("Failed to write to {}: {}", feed_path.display(), err);
+				exit(1)
 			}
+		}
+	}
+
+	output_root
+}
+
+/// Serves `output_root` over HTTP and rebuilds `cli.input` whenever it changes,
+/// bumping `version` so that pages with the live-reload script know to refresh.
+fn run_dev_server(cli: &Cli, output_root: &Path) {
+	let version = Arc::new(AtomicU64::new(0));
+
+	{
+		let cli = cli.clone();
+		let version = version.clone();
+		thread::spawn(move || {
+			let (tx, rx) = std::sync::mpsc::channel();
+			let mut watcher = match notify::recommended_watcher(tx) {
+				Ok(watcher) => watcher,
+				Err(err) => {
+					eprintln!("Failed to start file watcher: {err}");
+					return;
+				}
+			};
+
+			if let Err(err) = watcher.watch(&cli.input, RecursiveMode::Recursive) {
+				eprintln!("Failed to watch {}: {}", cli.input.display(), err);
+				return;
+			}
+
+			for event in rx {
+				if event.is_err() { continue; }
+				build_directory(&cli);
+				version.fetch_add(1, AtomicOrdering::SeqCst);
+				println!("Rebuilt site after a change to {}", cli.input.display());
+			}
+		});
+	}
 
-			let mut index_md_path = cli.input.to_path_buf();
-			index_md_path.push("index.md");
-			let mut index_html_path = cli.input.to_path_buf();
-			index_html_path.push("index.html");
+	let listener = match TcpListener::bind(("127.0.0.1", cli.port)) {
+		Ok(listener) => listener,
+		Err(err) => {
+			eprintln!("Failed to bind to port {}: {}", cli.port, err);
+			exit(1)
+		}
+	};
+
+	println!("Serving {} at http://127.0.0.1:{}", output_root.display(), cli.port);
+
+	for stream in listener.incoming() {
+		if let Ok(stream) = stream {
+			handle_connection(stream, output_root, &version);
+		}
+	}
+}
+
+/// Handles a single HTTP request: static files from `output_root`, plus a
+/// `/__evblog_reload` endpoint the live-reload script polls for `version`.
+fn handle_connection(mut stream: TcpStream, output_root: &Path, version: &Arc<AtomicU64>) {
+	let mut buffer = [0; 1024];
+	let bytes_read = match stream.read(&mut buffer) {
+		Ok(bytes_read) => bytes_read,
+		Err(_) => return,
+	};
+
+	let request = String::from_utf8_lossy(&buffer[..bytes_read]);
+	let path = request
+		.lines()
+		.next()
+		.and_then(|line| line.split_whitespace().nth(1))
+		.unwrap_or("/");
+
+	if path == "/__evblog_reload" {
+		let body = version.load(AtomicOrdering::SeqCst).to_string();
+		let _ = stream.write_all(http_response("200 OK", "text/plain", body.as_bytes()).as_slice());
+		return;
+	}
+
+	let mut file_path = output_root.join(path.trim_start_matches('/'));
+	if file_path.is_dir() {
+		file_path.push("index.html");
+	}
+
+	// Resolve both paths to block requests (e.g. containing `..`) that would
+	// otherwise escape output_root.
+	let canonical_file = match fs::canonicalize(&file_path) {
+		Ok(canonical_file) => canonical_file,
+		Err(_) => {
+			let _ = stream.write_all(http_response("404 Not Found", "text/plain", b"404 Not Found").as_slice());
+			return;
+		}
+	};
+
+	let canonical_root = fs::canonicalize(output_root).expect("output_root must exist");
+
+	if !canonical_file.starts_with(&canonical_root) {
+		let _ = stream.write_all(http_response("403 Forbidden", "text/plain", b"403 Forbidden").as_slice());
+		return;
+	}
+
+	match fs::read(&canonical_file) {
+		Ok(contents) => {
+			let content_type = match canonical_file.extension().and_then(OsStr::to_str) {
+				Some("html") => "text/html",
+				Some("css") => "text/css",
+				Some("js") => "text/javascript",
+				Some("xml") => "application/xml",
+				_ => "application/octet-stream",
+			};
+			let _ = stream.write_all(http_response("200 OK", content_type, &contents).as_slice());
+		}
+		Err(_) => {
+			let _ = stream.write_all(http_response("404 Not Found", "text/plain", b"404 Not Found").as_slice());
+		}
+	}
+}
+
+fn http_response(status: &str, content_type: &str, body: &[u8]) -> Vec<u8> {
+	let mut response = format!(
+		"HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\r\n",
+		body.len(),
+	).into_bytes();
+	response.extend_from_slice(body);
+	response
+}
+
+/// Resolves `path` to an absolute path and removes its `.`/`..` components
+/// lexically, without touching the filesystem. Unlike `fs::canonicalize`,
+/// this works even when `path` doesn't exist yet (e.g. an `--output`
+/// directory that a build will create), which is what callers that just
+/// need to compare two paths for containment want.
+fn normalize_path(path: &Path) -> PathBuf {
+	let path = if path.is_absolute() {
+		path.to_path_buf()
+	} else {
+		std::env::current_dir().unwrap().join(path)
+	};
 
-			if let Err(err) = fs::write(&index_md_path, index_md) {
-				eprintln!("Failed to write to {}: {}", index_md_path.display(), err);
+	let mut normalized = PathBuf::new();
+	for component in path.components() {
+		match component {
+			std::path::Component::ParentDir => { normalized.pop(); }
+			std::path::Component::CurDir => {}
+			component => normalized.push(component),
+		}
+	}
+	normalized
+}
+
+fn main() {
+	let cli = Cli::parse();
+
+	if cli.feed.is_some() && cli.index.is_none() {
+		eprintln!("--feed requires --index, since the feed reuses the index's title and base URL");
+		exit(1)
+	}
+
+	if cli.serve {
+		match &cli.output {
+			None => {
+				eprintln!("--serve requires --output, since serving from the input directory would rebuild it into itself on every change");
+				exit(1)
+			}
+			Some(output) if normalize_path(output).starts_with(normalize_path(&cli.input)) => {
+				eprintln!("--serve's --output must not be the input directory or nested under it, since rebuilds would write into the tree being watched and loop forever");
 				exit(1)
 			}
+			Some(_) => {}
+		}
+	}
+
+	if fs::metadata(&cli.input).unwrap().is_dir() {
+		let output_root = build_directory(&cli);
 
-			convert_document(&cli, &index_md_path, &index_html_path);
+		if cli.serve {
+			run_dev_server(&cli, &output_root);
 		}
 	} else {
 		let output = if let Some(ref output) = cli.output {
@@ -329,6 +878,9 @@ fn main() {
 			output
 		};
 
-		convert_document(&cli, &cli.input, &output);
+		if let Err(err) = convert_document(&cli, &cli.input, &output, false) {
+			eprintln!("{err}");
+			exit(1)
+		}
 	}
 }